@@ -246,6 +246,27 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Find running execution processes for all tasks in a project
+    pub async fn find_running_by_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcess,
+            r#"SELECT ep.id as "id!: Uuid", ep.task_attempt_id as "task_attempt_id!: Uuid", ep.run_reason as "run_reason!: ExecutionProcessRunReason", ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                      ep.before_head_commit, ep.after_head_commit, ep.status as "status!: ExecutionProcessStatus", ep.exit_code,
+                      ep.dropped, ep.started_at as "started_at!: DateTime<Utc>", ep.completed_at as "completed_at?: DateTime<Utc>", ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN task_attempts ta ON ep.task_attempt_id = ta.id
+               JOIN tasks t ON ta.task_id = t.id
+               WHERE ep.status = 'running' AND t.project_id = ?
+               ORDER BY ep.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find running dev servers for a specific project
     pub async fn find_running_dev_servers_by_project(
         pool: &SqlitePool,
@@ -611,3 +632,78 @@ impl ExecutionProcess {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+    use crate::test_support::{
+        seed_execution_process, seed_project, seed_task, seed_task_attempt, test_pool,
+    };
+
+    async fn seed_attempt(pool: &SqlitePool) -> (Uuid, Uuid) {
+        let project_id = seed_project(pool).await;
+        let task_id = seed_task(pool, project_id, "Task").await;
+        let attempt_id = seed_task_attempt(pool, task_id, BaseCodingAgent::ClaudeCode).await;
+        (project_id, attempt_id)
+    }
+
+    async fn seed_process(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        status: ExecutionProcessStatus,
+    ) -> Uuid {
+        seed_execution_process(
+            pool,
+            task_attempt_id,
+            ExecutionProcessRunReason::SetupScript,
+            status,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn find_running_by_project_only_returns_that_projects_running_processes() {
+        let pool = test_pool().await;
+        let (project_id, attempt_id) = seed_attempt(&pool).await;
+        let (other_project_id, other_attempt_id) = seed_attempt(&pool).await;
+
+        let running = seed_process(&pool, attempt_id, ExecutionProcessStatus::Running).await;
+        seed_process(&pool, attempt_id, ExecutionProcessStatus::Completed).await;
+        seed_process(&pool, attempt_id, ExecutionProcessStatus::Killed).await;
+        let other_running =
+            seed_process(&pool, other_attempt_id, ExecutionProcessStatus::Running).await;
+
+        let found: Vec<Uuid> = ExecutionProcess::find_running_by_project(&pool, project_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|process| process.id)
+            .collect();
+        assert_eq!(found, vec![running]);
+
+        let other_found: Vec<Uuid> =
+            ExecutionProcess::find_running_by_project(&pool, other_project_id)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|process| process.id)
+                .collect();
+        assert_eq!(other_found, vec![other_running]);
+    }
+
+    #[tokio::test]
+    async fn find_running_by_project_is_empty_when_nothing_runs() {
+        let pool = test_pool().await;
+        let (project_id, attempt_id) = seed_attempt(&pool).await;
+        seed_process(&pool, attempt_id, ExecutionProcessStatus::Completed).await;
+
+        assert!(
+            ExecutionProcess::find_running_by_project(&pool, project_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -84,7 +84,10 @@ impl Image {
         .await
     }
 
-    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+    pub async fn find_by_id<'e, E>(executor: E, id: Uuid) -> Result<Option<Self>, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         sqlx::query_as!(
             Image,
             r#"SELECT id as "id!: Uuid",
@@ -99,7 +102,7 @@ impl Image {
                WHERE id = $1"#,
             id
         )
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await
     }
 
@@ -185,6 +188,31 @@ impl TaskImage {
         Ok(())
     }
 
+    /// Associate an image with a task, skipping the insert if already associated.
+    pub async fn associate_dedup<'e, E>(
+        executor: E,
+        task_id: Uuid,
+        image_id: Uuid,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO task_images (id, task_id, image_id)
+               SELECT $1, $2, $3
+               WHERE NOT EXISTS (
+                   SELECT 1 FROM task_images WHERE task_id = $2 AND image_id = $3
+               )"#,
+            id,
+            task_id,
+            image_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
     /// Associate multiple images with a task, skipping duplicates.
     pub async fn associate_many_dedup(
         pool: &SqlitePool,
@@ -192,19 +220,7 @@ impl TaskImage {
         image_ids: &[Uuid],
     ) -> Result<(), sqlx::Error> {
         for &image_id in image_ids {
-            let id = Uuid::new_v4();
-            sqlx::query!(
-                r#"INSERT INTO task_images (id, task_id, image_id)
-                   SELECT $1, $2, $3
-                   WHERE NOT EXISTS (
-                       SELECT 1 FROM task_images WHERE task_id = $2 AND image_id = $3
-                   )"#,
-                id,
-                task_id,
-                image_id
-            )
-            .execute(pool)
-            .await?;
+            Self::associate_dedup(pool, task_id, image_id).await?;
         }
         Ok(())
     }
@@ -1,14 +1,43 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
+use futures_util::{Stream, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
-use strum_macros::{Display, EnumString};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::{project::Project, task_attempt::TaskAttempt};
+use super::{
+    image::{Image, TaskImage},
+    project::Project,
+    task_attempt::TaskAttempt,
+};
+
+#[derive(Debug, Error)]
+pub enum TaskError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Image not found: {0}")]
+    ImageNotFound(Uuid),
+}
 
 #[derive(
-    Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display, Default,
+    Debug,
+    Clone,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    TS,
+    EnumString,
+    EnumIter,
+    Display,
+    Default,
 )]
 #[sqlx(type_name = "task_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
@@ -148,7 +177,18 @@ impl Task {
         pool: &SqlitePool,
         project_id: Uuid,
     ) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
-        let records = sqlx::query!(
+        Self::stream_by_project_id_with_attempt_status(pool, &project_id)
+            .try_collect()
+            .await
+    }
+
+    /// Same rows as `find_by_project_id_with_attempt_status`, yielded as they are read
+    /// instead of being buffered into a `Vec`.
+    pub fn stream_by_project_id_with_attempt_status<'a>(
+        pool: &'a SqlitePool,
+        project_id: &'a Uuid,
+    ) -> impl Stream<Item = Result<TaskWithAttemptStatus, sqlx::Error>> + 'a {
+        sqlx::query!(
             r#"SELECT
   t.id                            AS "id!: Uuid",
   t.project_id                    AS "project_id!: Uuid",
@@ -193,33 +233,49 @@ impl Task {
 FROM tasks t
 WHERE t.project_id = $1
 ORDER BY t.created_at DESC"#,
+            *project_id
+        )
+        .fetch(pool)
+        .map_ok(|rec| TaskWithAttemptStatus {
+            task: Task {
+                id: rec.id,
+                project_id: rec.project_id,
+                title: rec.title,
+                description: rec.description,
+                status: rec.status,
+                parent_task_attempt: rec.parent_task_attempt,
+                shared_task_id: rec.shared_task_id,
+                created_at: rec.created_at,
+                updated_at: rec.updated_at,
+            },
+            has_in_progress_attempt: rec.has_in_progress_attempt != 0,
+            has_merged_attempt: false, // TODO use merges table
+            last_attempt_failed: rec.last_attempt_failed != 0,
+            executor: rec.executor,
+        })
+    }
+
+    /// Count a project's tasks per status; statuses without tasks are reported as 0.
+    pub async fn count_by_status(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<HashMap<TaskStatus, i64>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT status as "status!: TaskStatus", COUNT(*) as "count!: i64"
+               FROM tasks
+               WHERE project_id = $1
+               GROUP BY status"#,
             project_id
         )
         .fetch_all(pool)
         .await?;
 
-        let tasks = records
-            .into_iter()
-            .map(|rec| TaskWithAttemptStatus {
-                task: Task {
-                    id: rec.id,
-                    project_id: rec.project_id,
-                    title: rec.title,
-                    description: rec.description,
-                    status: rec.status,
-                    parent_task_attempt: rec.parent_task_attempt,
-                    shared_task_id: rec.shared_task_id,
-                    created_at: rec.created_at,
-                    updated_at: rec.updated_at,
-                },
-                has_in_progress_attempt: rec.has_in_progress_attempt != 0,
-                has_merged_attempt: false, // TODO use merges table
-                last_attempt_failed: rec.last_attempt_failed != 0,
-                executor: rec.executor,
-            })
-            .collect();
-
-        Ok(tasks)
+        let mut counts: HashMap<TaskStatus, i64> =
+            TaskStatus::iter().map(|status| (status, 0)).collect();
+        for row in rows {
+            counts.insert(row.status, row.count);
+        }
+        Ok(counts)
     }
 
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
@@ -282,11 +338,14 @@ ORDER BY t.created_at DESC"#,
         .await
     }
 
-    pub async fn create(
-        pool: &SqlitePool,
+    pub async fn create<'e, E>(
+        executor: E,
         data: &CreateTask,
         task_id: Uuid,
-    ) -> Result<Self, sqlx::Error> {
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         let status = data.status.clone().unwrap_or_default();
         sqlx::query_as!(
             Task,
@@ -301,10 +360,34 @@ ORDER BY t.created_at DESC"#,
             data.parent_task_attempt,
             data.shared_task_id
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
     }
 
+    /// Create a task and associate `data.image_ids` with it in one transaction.
+    /// Nothing is written if any of the referenced images does not exist.
+    pub async fn create_with_images(
+        pool: &SqlitePool,
+        data: &CreateTask,
+        task_id: Uuid,
+    ) -> Result<Self, TaskError> {
+        let mut tx = pool.begin().await?;
+
+        let task = Self::create(&mut *tx, data, task_id).await?;
+
+        if let Some(image_ids) = &data.image_ids {
+            for &image_id in image_ids {
+                if Image::find_by_id(&mut *tx, image_id).await?.is_none() {
+                    return Err(TaskError::ImageNotFound(image_id));
+                }
+                TaskImage::associate_dedup(&mut *tx, task.id, image_id).await?;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(task)
+    }
+
     pub async fn update(
         pool: &SqlitePool,
         id: Uuid,
@@ -317,7 +400,7 @@ ORDER BY t.created_at DESC"#,
         sqlx::query_as!(
             Task,
             r#"UPDATE tasks 
-               SET title = $3, description = $4, status = $5, parent_task_attempt = $6 
+               SET title = $3, description = $4, status = $5, parent_task_attempt = $6, updated_at = datetime('now', 'subsec')
                WHERE id = $1 AND project_id = $2 
                RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -389,7 +472,7 @@ ORDER BY t.created_at DESC"#,
         status: TaskStatus,
     ) -> Result<(), sqlx::Error> {
         sqlx::query!(
-            "UPDATE tasks SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            "UPDATE tasks SET status = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
             id,
             status
         )
@@ -457,7 +540,7 @@ ORDER BY t.created_at DESC"#,
         E: Executor<'e, Database = Sqlite>,
     {
         sqlx::query!(
-            "UPDATE tasks SET shared_task_id = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            "UPDATE tasks SET shared_task_id = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
             id,
             shared_task_id
         )
@@ -531,3 +614,112 @@ ORDER BY t.created_at DESC"#,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::image::CreateImage,
+        test_support::{seed_project, test_pool},
+    };
+
+    async fn seed_image(pool: &SqlitePool, name: &str) -> Uuid {
+        let data = CreateImage {
+            file_path: format!("{name}.png"),
+            original_name: format!("{name}.png"),
+            mime_type: Some("image/png".to_string()),
+            size_bytes: 1,
+            hash: name.to_string(),
+        };
+        Image::create(pool, &data).await.unwrap().id
+    }
+
+    #[tokio::test]
+    async fn update_bumps_updated_at() {
+        let pool = test_pool().await;
+        let project_id = seed_project(&pool).await;
+        let data = CreateTask::from_title_description(project_id, "Before".to_string(), None);
+        let task = Task::create(&pool, &data, Uuid::new_v4()).await.unwrap();
+
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        let updated = Task::update(
+            &pool,
+            task.id,
+            project_id,
+            "After".to_string(),
+            None,
+            TaskStatus::Todo,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert!(updated.updated_at > task.updated_at);
+    }
+
+    #[tokio::test]
+    async fn count_by_status_includes_empty_statuses() {
+        let pool = test_pool().await;
+        let project_id = seed_project(&pool).await;
+        let other_project_id = seed_project(&pool).await;
+        for (project, status) in [
+            (project_id, TaskStatus::Todo),
+            (project_id, TaskStatus::Todo),
+            (project_id, TaskStatus::InProgress),
+            (project_id, TaskStatus::Done),
+            (other_project_id, TaskStatus::Cancelled),
+        ] {
+            let data = CreateTask::from_title_description(project, "Task".to_string(), None);
+            let task = Task::create(&pool, &data, Uuid::new_v4()).await.unwrap();
+            Task::update_status(&pool, task.id, status).await.unwrap();
+        }
+
+        let counts = Task::count_by_status(&pool, project_id).await.unwrap();
+
+        assert_eq!(counts.len(), TaskStatus::iter().count());
+        assert_eq!(counts[&TaskStatus::Todo], 2);
+        assert_eq!(counts[&TaskStatus::InProgress], 1);
+        assert_eq!(counts[&TaskStatus::InReview], 0);
+        assert_eq!(counts[&TaskStatus::Done], 1);
+        assert_eq!(counts[&TaskStatus::Cancelled], 0);
+    }
+
+    #[tokio::test]
+    async fn create_with_images_associates_all_images() {
+        let pool = test_pool().await;
+        let project_id = seed_project(&pool).await;
+        let image_ids = vec![
+            seed_image(&pool, "first").await,
+            seed_image(&pool, "second").await,
+        ];
+
+        let mut data = CreateTask::from_title_description(project_id, "Task".to_string(), None);
+        data.image_ids = Some(image_ids.clone());
+        let task = Task::create_with_images(&pool, &data, Uuid::new_v4())
+            .await
+            .unwrap();
+
+        let attached: Vec<Uuid> = Image::find_by_task_id(&pool, task.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|image| image.id)
+            .collect();
+        assert_eq!(attached, image_ids);
+    }
+
+    #[tokio::test]
+    async fn create_with_images_rejects_unknown_image() {
+        let pool = test_pool().await;
+        let project_id = seed_project(&pool).await;
+        let missing = Uuid::new_v4();
+
+        let mut data = CreateTask::from_title_description(project_id, "Task".to_string(), None);
+        data.image_ids = Some(vec![seed_image(&pool, "valid").await, missing]);
+        let task_id = Uuid::new_v4();
+        let result = Task::create_with_images(&pool, &data, task_id).await;
+
+        assert!(matches!(result, Err(TaskError::ImageNotFound(id)) if id == missing));
+        assert!(Task::find_by_id(&pool, task_id).await.unwrap().is_none());
+    }
+}
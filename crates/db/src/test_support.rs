@@ -0,0 +1,109 @@
+//! Fixtures for tests in this crate and, via the `test-utils` feature, its dependents.
+
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType,
+        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+    },
+    executors::BaseCodingAgent,
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use uuid::Uuid;
+
+use crate::models::{
+    execution_process::{
+        CreateExecutionProcess, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
+    },
+    project::{CreateProject, Project},
+    task::{CreateTask, Task},
+    task_attempt::{CreateTaskAttempt, TaskAttempt},
+};
+
+/// In-memory database with all migrations applied.
+pub async fn test_pool() -> SqlitePool {
+    // A single connection keeps every query on the same in-memory database
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("failed to open in-memory database");
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("failed to run migrations");
+    pool
+}
+
+pub async fn seed_project(pool: &SqlitePool) -> Uuid {
+    let id = Uuid::new_v4();
+    let data = CreateProject {
+        name: "Test project".to_string(),
+        git_repo_path: format!("/tmp/test-repo-{id}"),
+        use_existing_repo: true,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+    };
+    Project::create(pool, &data, id).await.unwrap();
+    id
+}
+
+pub async fn seed_task(pool: &SqlitePool, project_id: Uuid, title: &str) -> Uuid {
+    let id = Uuid::new_v4();
+    let data = CreateTask::from_title_description(project_id, title.to_string(), None);
+    Task::create(pool, &data, id).await.unwrap();
+    id
+}
+
+pub async fn seed_task_attempt(
+    pool: &SqlitePool,
+    task_id: Uuid,
+    executor: BaseCodingAgent,
+) -> Uuid {
+    let data = CreateTaskAttempt {
+        executor,
+        base_branch: "main".to_string(),
+        branch: format!("test-{task_id}"),
+    };
+    TaskAttempt::create(pool, &data, Uuid::new_v4(), task_id)
+        .await
+        .unwrap()
+        .id
+}
+
+/// Creates an execution process for the attempt and moves it to `status`.
+pub async fn seed_execution_process(
+    pool: &SqlitePool,
+    task_attempt_id: Uuid,
+    run_reason: ExecutionProcessRunReason,
+    status: ExecutionProcessStatus,
+) -> Uuid {
+    let data = CreateExecutionProcess {
+        task_attempt_id,
+        executor_action: ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: "true".to_string(),
+                language: ScriptRequestLanguage::Bash,
+                context: ScriptContext::SetupScript,
+            }),
+            None,
+        ),
+        run_reason,
+    };
+    let id = Uuid::new_v4();
+    ExecutionProcess::create(pool, &data, id, None)
+        .await
+        .unwrap();
+    if status != ExecutionProcessStatus::Running {
+        let exit_code = if status == ExecutionProcessStatus::Completed {
+            0
+        } else {
+            1
+        };
+        ExecutionProcess::update_completion(pool, id, status, Some(exit_code))
+            .await
+            .unwrap();
+    }
+    id
+}
@@ -424,6 +424,27 @@ impl ExecutorConfigs {
         }
         Err(ProfileError::NoAvailableExecutorProfile)
     }
+
+    /// Return `executor_profile_id` if it resolves to a configured coding agent, otherwise the
+    /// closest usable fallback: the same executor's default variant, then Claude Code, then the
+    /// remaining executors' default variants in name order. Returns `None` only when no profile
+    /// is usable at all.
+    pub fn resolve_executor_profile(
+        &self,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> Option<ExecutorProfileId> {
+        // Sorted so the fallback, and the startup log naming it, is the same on every run
+        let mut remaining: Vec<BaseCodingAgent> = self.executors.keys().copied().collect();
+        remaining.sort_by_key(|agent| agent.to_string());
+
+        std::iter::once(executor_profile_id.clone())
+            .chain(std::iter::once(to_default_variant(executor_profile_id)))
+            .chain(std::iter::once(ExecutorProfileId::new(
+                BaseCodingAgent::ClaudeCode,
+            )))
+            .chain(remaining.into_iter().map(ExecutorProfileId::new))
+            .find(|profile_id| self.get_coding_agent(profile_id).is_some())
+    }
 }
 
 pub fn to_default_variant(id: &ExecutorProfileId) -> ExecutorProfileId {
@@ -432,3 +453,61 @@ pub fn to_default_variant(id: &ExecutorProfileId) -> ExecutorProfileId {
         variant: None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_executor_profile_keeps_valid_profile() {
+        let profiles = ExecutorConfigs::from_defaults();
+        let profile_id = ExecutorProfileId::new(BaseCodingAgent::Amp);
+
+        assert_eq!(
+            profiles.resolve_executor_profile(&profile_id),
+            Some(profile_id)
+        );
+    }
+
+    #[test]
+    fn resolve_executor_profile_falls_back_from_unknown_variant() {
+        let profiles = ExecutorConfigs::from_defaults();
+        let profile_id =
+            ExecutorProfileId::with_variant(BaseCodingAgent::Amp, "DOES_NOT_EXIST".to_string());
+
+        assert_eq!(
+            profiles.resolve_executor_profile(&profile_id),
+            Some(ExecutorProfileId::new(BaseCodingAgent::Amp))
+        );
+    }
+
+    #[test]
+    fn resolve_executor_profile_falls_back_from_missing_executor() {
+        let mut profiles = ExecutorConfigs::from_defaults();
+        profiles.executors.remove(&BaseCodingAgent::Amp);
+
+        let resolved = profiles
+            .resolve_executor_profile(&ExecutorProfileId::new(BaseCodingAgent::Amp))
+            .expect("defaults should always provide a usable profile");
+
+        assert_eq!(
+            resolved,
+            ExecutorProfileId::new(BaseCodingAgent::ClaudeCode)
+        );
+        assert!(profiles.get_coding_agent(&resolved).is_some());
+    }
+
+    #[test]
+    fn resolve_executor_profile_fallback_is_deterministic() {
+        let mut profiles = ExecutorConfigs::from_defaults();
+        profiles.executors.remove(&BaseCodingAgent::ClaudeCode);
+        let mut expected: Vec<BaseCodingAgent> = profiles.executors.keys().copied().collect();
+        expected.sort_by_key(|agent| agent.to_string());
+
+        let resolved = profiles
+            .resolve_executor_profile(&ExecutorProfileId::new(BaseCodingAgent::ClaudeCode))
+            .expect("defaults should provide other executors");
+
+        assert_eq!(resolved, ExecutorProfileId::new(expected[0]));
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use async_trait::async_trait;
 use db::DBService;
@@ -8,7 +8,7 @@ use services::services::{
     analytics::{AnalyticsConfig, AnalyticsContext, AnalyticsService, generate_user_id},
     approvals::Approvals,
     auth::AuthContext,
-    config::{Config, load_config_from_file, save_config_to_file},
+    config::{Config, ConfigError, load_config_from_file, save_config_to_file},
     container::ContainerService,
     drafts::DraftsService,
     events::EventService,
@@ -61,6 +61,35 @@ struct PendingHandoff {
     app_verifier: String,
 }
 
+/// Saves the startup config as-is, then returns the copy to run with, whose executor profile is
+/// replaced with a usable fallback if it no longer resolves. The fallback is never written to
+/// disk, so a profile that is only missing because profiles.json failed to parse survives.
+async fn save_startup_config(
+    mut config: Config,
+    config_path: &PathBuf,
+    profiles: &ExecutorConfigs,
+) -> Result<Config, ConfigError> {
+    save_config_to_file(&config, config_path).await?;
+    ensure_usable_executor_profile(&mut config, profiles);
+    Ok(config)
+}
+
+/// Replace the configured executor profile with a usable fallback if it no longer resolves.
+fn ensure_usable_executor_profile(config: &mut Config, profiles: &ExecutorConfigs) {
+    match profiles.resolve_executor_profile(&config.executor_profile) {
+        Some(resolved) if resolved != config.executor_profile => {
+            tracing::warn!(
+                "Executor profile {} is not configured, falling back to {}",
+                config.executor_profile,
+                resolved
+            );
+            config.executor_profile = resolved;
+        }
+        Some(_) => {}
+        None => tracing::error!("No usable executor profile is configured"),
+    }
+}
+
 #[async_trait]
 impl Deployment for LocalDeployment {
     async fn new() -> Result<Self, DeploymentError> {
@@ -85,8 +114,9 @@ impl Deployment for LocalDeployment {
             }
         }
 
-        // Always save config (may have been migrated or version updated)
-        save_config_to_file(&raw_config, &config_path()).await?;
+        // Always save config (may have been migrated or version updated), then make sure the
+        // profile we run with still exists, e.g. after a custom variant was removed
+        let raw_config = save_startup_config(raw_config, &config_path(), &profiles).await?;
 
         let config = Arc::new(RwLock::new(raw_config));
         let user_id = generate_user_id();
@@ -360,3 +390,68 @@ impl LocalDeployment {
         self.share_config.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+
+    use super::*;
+
+    #[test]
+    fn default_config_keeps_a_usable_profile() {
+        let profiles = ExecutorConfigs::from_defaults();
+        let mut config = Config::default();
+
+        ensure_usable_executor_profile(&mut config, &profiles);
+
+        assert_eq!(
+            config.executor_profile,
+            ExecutorProfileId::new(BaseCodingAgent::ClaudeCode)
+        );
+        assert!(
+            profiles
+                .get_coding_agent(&config.executor_profile)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn default_config_falls_back_when_its_executor_is_missing() {
+        let mut profiles = ExecutorConfigs::from_defaults();
+        profiles.executors.remove(&BaseCodingAgent::ClaudeCode);
+        let mut config = Config::default();
+
+        ensure_usable_executor_profile(&mut config, &profiles);
+
+        assert_ne!(
+            config.executor_profile,
+            ExecutorProfileId::new(BaseCodingAgent::ClaudeCode)
+        );
+        assert!(
+            profiles
+                .get_coding_agent(&config.executor_profile)
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_variant_is_not_written_back() {
+        let profiles = ExecutorConfigs::from_defaults();
+        let unknown =
+            ExecutorProfileId::with_variant(BaseCodingAgent::Amp, "DOES_NOT_EXIST".to_string());
+        let config = Config {
+            executor_profile: unknown.clone(),
+            ..Config::default()
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        let config = save_startup_config(config, &path, &profiles).await.unwrap();
+
+        assert_eq!(
+            config.executor_profile,
+            ExecutorProfileId::new(BaseCodingAgent::Amp)
+        );
+        assert_eq!(load_config_from_file(&path).await.executor_profile, unknown);
+    }
+}
@@ -5,7 +5,8 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use db::models::{
-    execution_process::ExecutionProcessError, project::ProjectError, task_attempt::TaskAttemptError,
+    execution_process::ExecutionProcessError, project::ProjectError, task::TaskError,
+    task_attempt::TaskAttemptError,
 };
 use deployment::{DeploymentError, RemoteClientNotConfigured};
 use executors::executors::ExecutorError;
@@ -18,12 +19,16 @@ use services::services::{
 use thiserror::Error;
 use utils::response::ApiResponse;
 
+use crate::middleware::current_request_id;
+
 #[derive(Debug, Error, ts_rs::TS)]
 #[ts(type = "string")]
 pub enum ApiError {
     #[error(transparent)]
     Project(#[from] ProjectError),
     #[error(transparent)]
+    Task(#[from] TaskError),
+    #[error(transparent)]
     TaskAttempt(#[from] TaskAttemptError),
     #[error(transparent)]
     ExecutionProcess(#[from] ExecutionProcessError),
@@ -83,85 +88,224 @@ impl From<RemoteClientNotConfigured> for ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let (status_code, error_type) = match &self {
-            ApiError::Project(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ProjectError"),
-            ApiError::TaskAttempt(_) => (StatusCode::INTERNAL_SERVER_ERROR, "TaskAttemptError"),
+        // `error_type` labels the human-readable message; `code` is the stable,
+        // machine-readable identifier clients can branch on.
+        let (status_code, error_type, code) = match &self {
+            ApiError::Project(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "ProjectError",
+                "project_error",
+            ),
+            ApiError::Task(err) => match err {
+                TaskError::ImageNotFound(_) => (
+                    StatusCode::BAD_REQUEST,
+                    "ImageNotFound",
+                    "task_image_not_found",
+                ),
+                TaskError::Database(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "TaskError",
+                    "task_database_error",
+                ),
+            },
+            ApiError::TaskAttempt(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "TaskAttemptError",
+                "task_attempt_error",
+            ),
             ApiError::ExecutionProcess(err) => match err {
-                ExecutionProcessError::ExecutionProcessNotFound => {
-                    (StatusCode::NOT_FOUND, "ExecutionProcessError")
-                }
-                _ => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutionProcessError"),
+                ExecutionProcessError::ExecutionProcessNotFound => (
+                    StatusCode::NOT_FOUND,
+                    "ExecutionProcessError",
+                    "execution_process_not_found",
+                ),
+                _ => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "ExecutionProcessError",
+                    "execution_process_error",
+                ),
             },
             // Promote certain GitService errors to conflict status with concise messages
             ApiError::GitService(git_err) => match git_err {
                 services::services::git::GitServiceError::MergeConflicts(_) => {
-                    (StatusCode::CONFLICT, "GitServiceError")
-                }
-                services::services::git::GitServiceError::RebaseInProgress => {
-                    (StatusCode::CONFLICT, "GitServiceError")
+                    (StatusCode::CONFLICT, "GitServiceError", "merge_conflicts")
                 }
-                _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitServiceError"),
+                services::services::git::GitServiceError::RebaseInProgress => (
+                    StatusCode::CONFLICT,
+                    "GitServiceError",
+                    "rebase_in_progress",
+                ),
+                _ => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "GitServiceError",
+                    "git_error",
+                ),
             },
-            ApiError::GitHubService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError"),
-            ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
-            ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
-            ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
-            ApiError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError"),
-            ApiError::Worktree(_) => (StatusCode::INTERNAL_SERVER_ERROR, "WorktreeError"),
-            ApiError::Config(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ConfigError"),
+            ApiError::GitHubService(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "GitHubServiceError",
+                "github_error",
+            ),
+            ApiError::Deployment(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DeploymentError",
+                "deployment_error",
+            ),
+            ApiError::Container(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "ContainerError",
+                "container_error",
+            ),
+            ApiError::Executor(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "ExecutorError",
+                "executor_error",
+            ),
+            ApiError::Database(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "DatabaseError",
+                "database_error",
+            ),
+            ApiError::Worktree(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "WorktreeError",
+                "worktree_error",
+            ),
+            ApiError::Config(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "ConfigError",
+                "config_error",
+            ),
             ApiError::Image(img_err) => match img_err {
-                ImageError::InvalidFormat => (StatusCode::BAD_REQUEST, "InvalidImageFormat"),
-                ImageError::TooLarge(_, _) => (StatusCode::PAYLOAD_TOO_LARGE, "ImageTooLarge"),
-                ImageError::NotFound => (StatusCode::NOT_FOUND, "ImageNotFound"),
-                _ => (StatusCode::INTERNAL_SERVER_ERROR, "ImageError"),
+                ImageError::InvalidFormat => (
+                    StatusCode::BAD_REQUEST,
+                    "InvalidImageFormat",
+                    "invalid_image_format",
+                ),
+                ImageError::TooLarge(_, _) => (
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    "ImageTooLarge",
+                    "image_too_large",
+                ),
+                ImageError::NotFound => (StatusCode::NOT_FOUND, "ImageNotFound", "image_not_found"),
+                _ => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "ImageError",
+                    "image_error",
+                ),
             },
             ApiError::Drafts(drafts_err) => match drafts_err {
-                DraftsServiceError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
-                DraftsServiceError::Database(_) => {
-                    (StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError")
-                }
-                DraftsServiceError::Container(_) => {
-                    (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError")
-                }
-                DraftsServiceError::Image(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ImageError"),
-                DraftsServiceError::ExecutionProcess(_) => {
-                    (StatusCode::INTERNAL_SERVER_ERROR, "ExecutionProcessError")
+                DraftsServiceError::Conflict(_) => {
+                    (StatusCode::CONFLICT, "ConflictError", "draft_conflict")
                 }
+                DraftsServiceError::Database(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "DatabaseError",
+                    "draft_database_error",
+                ),
+                DraftsServiceError::Container(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "ContainerError",
+                    "draft_container_error",
+                ),
+                DraftsServiceError::Image(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "ImageError",
+                    "draft_image_error",
+                ),
+                DraftsServiceError::ExecutionProcess(_) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "ExecutionProcessError",
+                    "draft_execution_process_error",
+                ),
             },
-            ApiError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "IoError"),
-            ApiError::Multipart(_) => (StatusCode::BAD_REQUEST, "MultipartError"),
+            ApiError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "IoError", "io_error"),
+            ApiError::Multipart(_) => {
+                (StatusCode::BAD_REQUEST, "MultipartError", "multipart_error")
+            }
             ApiError::RemoteClient(err) => match err {
-                RemoteClientError::Auth => (StatusCode::UNAUTHORIZED, "RemoteClientError"),
-                RemoteClientError::Timeout => (StatusCode::GATEWAY_TIMEOUT, "RemoteClientError"),
-                RemoteClientError::Transport(_) => (StatusCode::BAD_GATEWAY, "RemoteClientError"),
+                RemoteClientError::Auth => (
+                    StatusCode::UNAUTHORIZED,
+                    "RemoteClientError",
+                    "remote_unauthorized",
+                ),
+                RemoteClientError::Timeout => (
+                    StatusCode::GATEWAY_TIMEOUT,
+                    "RemoteClientError",
+                    "remote_timeout",
+                ),
+                RemoteClientError::Transport(_) => (
+                    StatusCode::BAD_GATEWAY,
+                    "RemoteClientError",
+                    "remote_unavailable",
+                ),
                 RemoteClientError::Http { status, .. } => (
                     StatusCode::from_u16(*status).unwrap_or(StatusCode::BAD_GATEWAY),
                     "RemoteClientError",
+                    "remote_http_error",
                 ),
                 RemoteClientError::Api(code) => match code {
-                    services::services::remote_client::HandoffErrorCode::NotFound => {
-                        (StatusCode::NOT_FOUND, "RemoteClientError")
-                    }
-                    services::services::remote_client::HandoffErrorCode::Expired => {
-                        (StatusCode::UNAUTHORIZED, "RemoteClientError")
-                    }
-                    services::services::remote_client::HandoffErrorCode::AccessDenied => {
-                        (StatusCode::FORBIDDEN, "RemoteClientError")
-                    }
-                    services::services::remote_client::HandoffErrorCode::ProviderError
-                    | services::services::remote_client::HandoffErrorCode::InternalError => {
-                        (StatusCode::BAD_GATEWAY, "RemoteClientError")
-                    }
-                    _ => (StatusCode::BAD_REQUEST, "RemoteClientError"),
+                    services::services::remote_client::HandoffErrorCode::NotFound => (
+                        StatusCode::NOT_FOUND,
+                        "RemoteClientError",
+                        "handoff_not_found",
+                    ),
+                    services::services::remote_client::HandoffErrorCode::Expired => (
+                        StatusCode::UNAUTHORIZED,
+                        "RemoteClientError",
+                        "handoff_expired",
+                    ),
+                    services::services::remote_client::HandoffErrorCode::AccessDenied => (
+                        StatusCode::FORBIDDEN,
+                        "RemoteClientError",
+                        "handoff_access_denied",
+                    ),
+                    services::services::remote_client::HandoffErrorCode::ProviderError => (
+                        StatusCode::BAD_GATEWAY,
+                        "RemoteClientError",
+                        "handoff_provider_error",
+                    ),
+                    services::services::remote_client::HandoffErrorCode::InternalError => (
+                        StatusCode::BAD_GATEWAY,
+                        "RemoteClientError",
+                        "handoff_internal_error",
+                    ),
+                    services::services::remote_client::HandoffErrorCode::UnsupportedProvider => (
+                        StatusCode::BAD_REQUEST,
+                        "RemoteClientError",
+                        "handoff_unsupported_provider",
+                    ),
+                    services::services::remote_client::HandoffErrorCode::InvalidReturnUrl => (
+                        StatusCode::BAD_REQUEST,
+                        "RemoteClientError",
+                        "handoff_invalid_return_url",
+                    ),
+                    services::services::remote_client::HandoffErrorCode::InvalidChallenge => (
+                        StatusCode::BAD_REQUEST,
+                        "RemoteClientError",
+                        "handoff_invalid_challenge",
+                    ),
+                    services::services::remote_client::HandoffErrorCode::Other(_) => (
+                        StatusCode::BAD_REQUEST,
+                        "RemoteClientError",
+                        "handoff_error",
+                    ),
                 },
-                RemoteClientError::Serde(_) | RemoteClientError::Url(_) => {
-                    (StatusCode::BAD_REQUEST, "RemoteClientError")
-                }
+                RemoteClientError::Serde(_) => (
+                    StatusCode::BAD_REQUEST,
+                    "RemoteClientError",
+                    "remote_invalid_response",
+                ),
+                RemoteClientError::Url(_) => (
+                    StatusCode::BAD_REQUEST,
+                    "RemoteClientError",
+                    "remote_invalid_url",
+                ),
             },
-            ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized"),
-            ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest"),
-            ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
-            ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "ForbiddenError"),
+            ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized", "unauthorized"),
+            ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest", "bad_request"),
+            ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError", "conflict"),
+            ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "ForbiddenError", "forbidden"),
         };
 
         let error_message = match &self {
@@ -177,6 +321,9 @@ impl IntoResponse for ApiError {
                     "Failed to process image. Please try again.".to_string()
                 }
             },
+            ApiError::Task(TaskError::ImageNotFound(_)) => {
+                "One of the attached images could not be found. Please re-upload it and try again.".to_string()
+            }
             ApiError::GitService(git_err) => match git_err {
                 services::services::git::GitServiceError::MergeConflicts(msg) => msg.clone(),
                 services::services::git::GitServiceError::RebaseInProgress => {
@@ -243,7 +390,8 @@ impl IntoResponse for ApiError {
             },
             _ => format!("{}: {}", error_type, self),
         };
-        let response = ApiResponse::<()>::error(&error_message);
+        let response = ApiResponse::<()>::error_with_code(&error_message, code)
+            .with_request_id(current_request_id());
         (status_code, Json(response)).into_response()
     }
 }
@@ -304,3 +452,112 @@ impl From<ShareError> for ApiError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use services::services::remote_client::HandoffErrorCode;
+
+    use super::*;
+
+    async fn response_code(error: ApiError) -> (StatusCode, serde_json::Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        (status, body["code"].clone())
+    }
+
+    #[tokio::test]
+    async fn each_failure_has_a_distinct_code() {
+        let io_error = || std::io::Error::other("boom");
+        let errors = vec![
+            ApiError::Project(ProjectError::ProjectNotFound),
+            ApiError::Task(TaskError::ImageNotFound(uuid::Uuid::new_v4())),
+            ApiError::Task(TaskError::Database(sqlx::Error::RowNotFound)),
+            ApiError::TaskAttempt(TaskAttemptError::TaskNotFound),
+            ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound),
+            ApiError::ExecutionProcess(ExecutionProcessError::InvalidExecutorAction),
+            ApiError::GitService(GitServiceError::MergeConflicts("conflict".to_string())),
+            ApiError::GitService(GitServiceError::RebaseInProgress),
+            ApiError::GitService(GitServiceError::BranchNotFound("main".to_string())),
+            ApiError::GitHubService(GitHubServiceError::TokenInvalid),
+            ApiError::Deployment(DeploymentError::Io(io_error())),
+            ApiError::Container(ContainerError::Sqlx(sqlx::Error::RowNotFound)),
+            ApiError::Executor(ExecutorError::UnknownExecutorType("x".to_string())),
+            ApiError::Database(sqlx::Error::RowNotFound),
+            ApiError::Worktree(WorktreeError::InvalidPath("x".to_string())),
+            ApiError::Config(ConfigError::ValidationError("x".to_string())),
+            ApiError::Image(ImageError::InvalidFormat),
+            ApiError::Image(ImageError::TooLarge(2, 1)),
+            ApiError::Image(ImageError::NotFound),
+            ApiError::Image(ImageError::Io(io_error())),
+            ApiError::Drafts(DraftsServiceError::Conflict("x".to_string())),
+            ApiError::Drafts(DraftsServiceError::Database(sqlx::Error::RowNotFound)),
+            ApiError::Drafts(DraftsServiceError::Container(ContainerError::Sqlx(
+                sqlx::Error::RowNotFound,
+            ))),
+            ApiError::Drafts(DraftsServiceError::Image(ImageError::NotFound)),
+            ApiError::Drafts(DraftsServiceError::ExecutionProcess(
+                ExecutionProcessError::ExecutionProcessNotFound,
+            )),
+            ApiError::Io(io_error()),
+            ApiError::RemoteClient(RemoteClientError::Auth),
+            ApiError::RemoteClient(RemoteClientError::Timeout),
+            ApiError::RemoteClient(RemoteClientError::Transport("x".to_string())),
+            ApiError::RemoteClient(RemoteClientError::Http {
+                status: 502,
+                body: String::new(),
+            }),
+            ApiError::RemoteClient(RemoteClientError::Api(HandoffErrorCode::NotFound)),
+            ApiError::RemoteClient(RemoteClientError::Api(HandoffErrorCode::Expired)),
+            ApiError::RemoteClient(RemoteClientError::Api(HandoffErrorCode::AccessDenied)),
+            ApiError::RemoteClient(RemoteClientError::Api(HandoffErrorCode::ProviderError)),
+            ApiError::RemoteClient(RemoteClientError::Api(HandoffErrorCode::InternalError)),
+            ApiError::RemoteClient(RemoteClientError::Api(
+                HandoffErrorCode::UnsupportedProvider,
+            )),
+            ApiError::RemoteClient(RemoteClientError::Api(HandoffErrorCode::InvalidReturnUrl)),
+            ApiError::RemoteClient(RemoteClientError::Api(HandoffErrorCode::InvalidChallenge)),
+            ApiError::RemoteClient(RemoteClientError::Api(HandoffErrorCode::Other(
+                "x".to_string(),
+            ))),
+            ApiError::RemoteClient(RemoteClientError::Serde("x".to_string())),
+            ApiError::RemoteClient(RemoteClientError::Url("x".to_string())),
+            ApiError::Unauthorized,
+            ApiError::BadRequest("x".to_string()),
+            ApiError::Conflict("x".to_string()),
+            ApiError::Forbidden("x".to_string()),
+        ];
+
+        let mut seen: HashMap<String, String> = HashMap::new();
+        for error in errors {
+            let label = format!("{error:?}");
+            let (_, code) = response_code(error).await;
+            let code = code.as_str().expect("every error has a code").to_string();
+            if let Some(previous) = seen.insert(code.clone(), label.clone()) {
+                panic!("`{code}` is used by both {previous} and {label}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn not_found_errors_keep_their_status() {
+        let (status, code) = response_code(ApiError::ExecutionProcess(
+            ExecutionProcessError::ExecutionProcessNotFound,
+        ))
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(code, "execution_process_not_found");
+
+        let (status, code) = response_code(ApiError::Task(TaskError::ImageNotFound(
+            uuid::Uuid::new_v4(),
+        )))
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(code, "task_image_not_found");
+    }
+}
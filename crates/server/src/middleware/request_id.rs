@@ -0,0 +1,29 @@
+use axum::{extract::Request, middleware::Next, response::Response};
+use tower_http::request_id::RequestId;
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+/// Makes the request's X-Request-Id readable through `current_request_id` while the rest of
+/// the stack (including `ApiError::into_response`) runs. Must sit inside `SetRequestIdLayer`.
+pub async fn scope_request_id(request: Request, next: Next) -> Response {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .and_then(|id| id.header_value().to_str().ok())
+        .map(str::to_owned);
+    match request_id {
+        Some(request_id) => {
+            CURRENT_REQUEST_ID
+                .scope(request_id, next.run(request))
+                .await
+        }
+        None => next.run(request).await,
+    }
+}
+
+/// The id of the request currently being handled, if any.
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+}
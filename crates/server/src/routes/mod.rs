@@ -1,9 +1,16 @@
 use axum::{
     Router,
+    http::{Request, header::HeaderName},
+    middleware::from_fn,
     routing::{IntoMakeService, get},
 };
+use tower_http::{
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, RequestId, SetRequestIdLayer},
+    trace::{DefaultOnFailure, DefaultOnResponse, TraceLayer},
+};
+use tracing::{Level, field};
 
-use crate::DeploymentImpl;
+use crate::{DeploymentImpl, middleware::scope_request_id};
 
 pub mod approvals;
 pub mod config;
@@ -45,9 +52,110 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .nest("/images", images::routes())
         .with_state(deployment);
 
-    Router::new()
+    let app = Router::new()
         .route("/", get(frontend::serve_frontend_root))
         .route("/{*path}", get(frontend::serve_frontend))
-        .nest("/api", base_routes)
-        .into_make_service()
+        .nest("/api", base_routes);
+
+    with_request_id(app).into_make_service()
+}
+
+/// Every request gets an X-Request-Id (the caller's, if supplied) which is recorded on the
+/// request span, included in `ApiError` bodies and echoed back on the response, so log lines
+/// can be correlated.
+fn with_request_id(router: Router) -> Router {
+    let trace_layer = TraceLayer::new_for_http()
+        .make_span_with(|request: &Request<_>| {
+            let request_id = request
+                .extensions()
+                .get::<RequestId>()
+                .and_then(|id| id.header_value().to_str().ok());
+            let span = tracing::info_span!(
+                "http_request",
+                method = %request.method(),
+                uri = %request.uri(),
+                request_id = field::Empty
+            );
+            if let Some(request_id) = request_id {
+                span.record("request_id", field::display(request_id));
+            }
+            span
+        })
+        .on_response(DefaultOnResponse::new().level(Level::DEBUG))
+        .on_failure(DefaultOnFailure::new().level(Level::ERROR));
+
+    router
+        .layer(from_fn(scope_request_id))
+        .layer(trace_layer)
+        .layer(PropagateRequestIdLayer::new(HeaderName::from_static(
+            "x-request-id",
+        )))
+        .layer(SetRequestIdLayer::new(
+            HeaderName::from_static("x-request-id"),
+            MakeRequestUuid,
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::error::ApiError;
+
+    fn health_app() -> Router {
+        with_request_id(
+            Router::new()
+                .route("/api/health", get(health::health_check))
+                .route(
+                    "/api/fail",
+                    get(|| async { Err::<(), _>(ApiError::BadRequest("nope".to_string())) }),
+                ),
+        )
+    }
+
+    #[tokio::test]
+    async fn supplied_request_id_is_echoed() {
+        let request = Request::builder()
+            .uri("/api/health")
+            .header("x-request-id", "client-supplied-id")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = health_app().oneshot(request).await.unwrap();
+
+        assert_eq!(response.headers()["x-request-id"], "client-supplied-id");
+    }
+
+    #[tokio::test]
+    async fn request_id_is_generated_when_absent() {
+        let request = Request::builder()
+            .uri("/api/health")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = health_app().oneshot(request).await.unwrap();
+
+        let request_id = response.headers()["x-request-id"].to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(request_id).is_ok());
+    }
+
+    #[tokio::test]
+    async fn error_body_carries_supplied_request_id() {
+        let request = Request::builder()
+            .uri("/api/fail")
+            .header("x-request-id", "client-supplied-id")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = health_app().oneshot(request).await.unwrap();
+
+        assert_eq!(response.headers()["x-request-id"], "client-supplied-id");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["request_id"], "client-supplied-id");
+    }
 }
@@ -16,6 +16,7 @@ use deployment::Deployment;
 use ignore::WalkBuilder;
 use serde::Deserialize;
 use services::services::{
+    container::ContainerService,
     file_ranker::FileRanker,
     file_search_cache::{CacheError, SearchMode, SearchQuery},
     git::GitBranch,
@@ -376,6 +377,20 @@ pub async fn delete_project(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
+    // Stop any running agents/scripts before their rows are cascaded away
+    if let Err(e) = deployment
+        .container()
+        .stop_project_processes(project.id)
+        .await
+    {
+        tracing::error!(
+            "Failed to stop running processes for project {}: {}",
+            project.id,
+            e
+        );
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
     match Project::delete(&deployment.db().pool, project.id).await {
         Ok(rows_affected) => {
             if rows_affected == 0 {
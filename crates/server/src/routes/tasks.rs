@@ -1,20 +1,21 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use anyhow;
 use axum::{
-    Extension, Json, Router,
+    BoxError, Extension, Json, Router,
+    body::Body,
     extract::{
         Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
+    http::{StatusCode, header},
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
     routing::{delete, get, post, put},
 };
 use db::models::{
     image::TaskImage,
-    task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
+    task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus, UpdateTask},
     task_attempt::{CreateTaskAttempt, TaskAttempt},
 };
 use deployment::Deployment;
@@ -25,7 +26,7 @@ use services::services::{
     container::{ContainerService, WorktreeCleanupData, cleanup_worktrees_direct},
     share::ShareError,
 };
-use sqlx::Error as SqlxError;
+use sqlx::{Error as SqlxError, SqlitePool};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -48,6 +49,56 @@ pub async fn get_tasks(
     Ok(ResponseJson(ApiResponse::success(tasks)))
 }
 
+/// Streams the project's tasks as NDJSON, one `TaskWithAttemptStatus` per line, without
+/// buffering the whole list in memory.
+pub async fn stream_tasks_ndjson(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskQuery>,
+) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        tasks_ndjson_body(deployment.db().pool.clone(), query.project_id),
+    )
+}
+
+/// Reads the project's tasks on a background task and forwards each row as an NDJSON line.
+/// A database or serialization error is sent as the final item, which aborts the body.
+fn tasks_ndjson_body(pool: SqlitePool, project_id: Uuid) -> Body {
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<String, BoxError>>(32);
+
+    tokio::spawn(async move {
+        let mut tasks = std::pin::pin!(Task::stream_by_project_id_with_attempt_status(
+            &pool,
+            &project_id
+        ));
+        while let Some(task) = tasks.next().await {
+            let line = task.map_err(BoxError::from).and_then(|task| {
+                let mut line = serde_json::to_string(&task)?;
+                line.push('\n');
+                Ok(line)
+            });
+            let failed = line.is_err();
+            // Stop reading rows once the client has gone away or the stream has failed
+            if tx.send(line).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    Body::from_stream(futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|line| (line, rx))
+    }))
+}
+
+pub async fn get_task_counts(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskQuery>,
+) -> Result<ResponseJson<ApiResponse<HashMap<TaskStatus, i64>>>, ApiError> {
+    let counts = Task::count_by_status(&deployment.db().pool, query.project_id).await?;
+
+    Ok(ResponseJson(ApiResponse::success(counts)))
+}
+
 pub async fn stream_tasks_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -114,11 +165,7 @@ pub async fn create_task(
         payload.project_id
     );
 
-    let task = Task::create(&deployment.db().pool, &payload, id).await?;
-
-    if let Some(image_ids) = &payload.image_ids {
-        TaskImage::associate_many_dedup(&deployment.db().pool, task.id, image_ids).await?;
-    }
+    let task = Task::create_with_images(&deployment.db().pool, &payload, id).await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -147,11 +194,7 @@ pub async fn create_task_and_start(
     Json(payload): Json<CreateAndStartTaskRequest>,
 ) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
     let task_id = Uuid::new_v4();
-    let task = Task::create(&deployment.db().pool, &payload.task, task_id).await?;
-
-    if let Some(image_ids) = &payload.task.image_ids {
-        TaskImage::associate_many(&deployment.db().pool, task.id, image_ids).await?;
-    }
+    let task = Task::create_with_images(&deployment.db().pool, &payload.task, task_id).await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -418,6 +461,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let inner = Router::new()
         .route("/", get(get_tasks).post(create_task))
+        .route("/counts", get(get_task_counts))
+        .route("/stream/ndjson", get(stream_tasks_ndjson))
         .route("/stream/ws", get(stream_tasks_ws))
         .route("/create-and-start", post(create_task_and_start))
         .nest("/{task_id}", task_id_router);
@@ -425,3 +470,88 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     // mount under /projects/:project_id/tasks
     Router::new().nest("/tasks", inner)
 }
+
+#[cfg(test)]
+mod tests {
+    use db::{
+        models::execution_process::{ExecutionProcessRunReason, ExecutionProcessStatus},
+        test_support::{
+            seed_execution_process, seed_project, seed_task, seed_task_attempt, test_pool,
+        },
+    };
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    /// Creates a task with one attempt whose coding agent process ends in `status`.
+    async fn seed_task_with_attempt(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        title: &str,
+        executor: BaseCodingAgent,
+        status: ExecutionProcessStatus,
+    ) {
+        let task_id = seed_task(pool, project_id, title).await;
+        let attempt_id = seed_task_attempt(pool, task_id, executor).await;
+        seed_execution_process(
+            pool,
+            attempt_id,
+            ExecutionProcessRunReason::CodingAgent,
+            status,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn ndjson_stream_matches_task_list() {
+        let pool = test_pool().await;
+        let project_id = seed_project(&pool).await;
+        seed_task_with_attempt(
+            &pool,
+            project_id,
+            "running",
+            BaseCodingAgent::ClaudeCode,
+            ExecutionProcessStatus::Running,
+        )
+        .await;
+        seed_task_with_attempt(
+            &pool,
+            project_id,
+            "failed",
+            BaseCodingAgent::Amp,
+            ExecutionProcessStatus::Failed,
+        )
+        .await;
+
+        let body = axum::body::to_bytes(tasks_ndjson_body(pool.clone(), project_id), usize::MAX)
+            .await
+            .unwrap();
+        let streamed: Vec<serde_json::Value> = std::str::from_utf8(&body)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        let listed = Task::find_by_project_id_with_attempt_status(&pool, project_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            streamed,
+            serde_json::to_value(&listed)
+                .unwrap()
+                .as_array()
+                .unwrap()
+                .clone()
+        );
+
+        let running = streamed.iter().find(|t| t["title"] == "running").unwrap();
+        assert_eq!(running["has_in_progress_attempt"], true);
+        assert_eq!(running["last_attempt_failed"], false);
+        assert_eq!(running["executor"], "CLAUDE_CODE");
+
+        let failed = streamed.iter().find(|t| t["title"] == "failed").unwrap();
+        assert_eq!(failed["has_in_progress_attempt"], false);
+        assert_eq!(failed["last_attempt_failed"], true);
+        assert_eq!(failed["executor"], "AMP");
+    }
+}
@@ -154,6 +154,26 @@ pub trait ContainerService {
         Ok(())
     }
 
+    /// Stop all running execution processes for a project's tasks.
+    /// Safe to call when nothing is running.
+    async fn stop_project_processes(&self, project_id: Uuid) -> Result<(), ContainerError> {
+        let processes =
+            ExecutionProcess::find_running_by_project(&self.db().pool, project_id).await?;
+        for process in processes {
+            self.stop_execution(&process, ExecutionProcessStatus::Killed)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!(
+                        "Failed to stop execution process {} for project {}: {}",
+                        process.id,
+                        project_id,
+                        e
+                    );
+                });
+        }
+        Ok(())
+    }
+
     fn cleanup_action(&self, cleanup_script: Option<String>) -> Option<Box<ExecutorAction>> {
         cleanup_script.map(|script| {
             Box::new(ExecutorAction::new(
@@ -832,3 +852,223 @@ pub trait ContainerService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use db::test_support::{
+        seed_execution_process, seed_project, seed_task, seed_task_attempt, test_pool,
+    };
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    /// Records every `stop_execution` call; only the DB-backed accessors are implemented.
+    struct StubContainer {
+        db: DBService,
+        git: GitService,
+        msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+        stopped: Mutex<Vec<(Uuid, ExecutionProcessStatus)>>,
+        fail_on: Option<Uuid>,
+    }
+
+    impl StubContainer {
+        fn new(pool: sqlx::SqlitePool, fail_on: Option<Uuid>) -> Self {
+            Self {
+                db: DBService { pool },
+                git: GitService::new(),
+                msg_stores: Arc::new(RwLock::new(HashMap::new())),
+                stopped: Mutex::new(Vec::new()),
+                fail_on,
+            }
+        }
+
+        fn stopped(&self) -> Vec<(Uuid, ExecutionProcessStatus)> {
+            self.stopped.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl ContainerService for StubContainer {
+        fn msg_stores(&self) -> &Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>> {
+            &self.msg_stores
+        }
+
+        fn db(&self) -> &DBService {
+            &self.db
+        }
+
+        fn git(&self) -> &GitService {
+            &self.git
+        }
+
+        fn share_publisher(&self) -> Option<&SharePublisher> {
+            None
+        }
+
+        fn task_attempt_to_current_dir(&self, _: &TaskAttempt) -> PathBuf {
+            unimplemented!()
+        }
+
+        async fn create(&self, _: &TaskAttempt) -> Result<ContainerRef, ContainerError> {
+            unimplemented!()
+        }
+
+        async fn kill_all_running_processes(&self) -> Result<(), ContainerError> {
+            unimplemented!()
+        }
+
+        async fn delete_inner(&self, _: &TaskAttempt) -> Result<(), ContainerError> {
+            unimplemented!()
+        }
+
+        async fn ensure_container_exists(
+            &self,
+            _: &TaskAttempt,
+        ) -> Result<ContainerRef, ContainerError> {
+            unimplemented!()
+        }
+
+        async fn is_container_clean(&self, _: &TaskAttempt) -> Result<bool, ContainerError> {
+            unimplemented!()
+        }
+
+        async fn start_execution_inner(
+            &self,
+            _: &TaskAttempt,
+            _: &ExecutionProcess,
+            _: &ExecutorAction,
+        ) -> Result<(), ContainerError> {
+            unimplemented!()
+        }
+
+        async fn stop_execution(
+            &self,
+            execution_process: &ExecutionProcess,
+            status: ExecutionProcessStatus,
+        ) -> Result<(), ContainerError> {
+            self.stopped
+                .lock()
+                .unwrap()
+                .push((execution_process.id, status));
+            if self.fail_on == Some(execution_process.id) {
+                return Err(ContainerError::Other(anyhow!("stub stop failure")));
+            }
+            Ok(())
+        }
+
+        async fn try_commit_changes(&self, _: &ExecutionContext) -> Result<bool, ContainerError> {
+            unimplemented!()
+        }
+
+        async fn copy_project_files(
+            &self,
+            _: &Path,
+            _: &Path,
+            _: &str,
+        ) -> Result<(), ContainerError> {
+            unimplemented!()
+        }
+
+        async fn stream_diff(
+            &self,
+            _: &TaskAttempt,
+            _: bool,
+        ) -> Result<
+            futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>,
+            ContainerError,
+        > {
+            unimplemented!()
+        }
+
+        async fn git_branch_prefix(&self) -> String {
+            unimplemented!()
+        }
+    }
+
+    /// Seeds a project with one attempt holding a process in each of `statuses`.
+    async fn seed_project_processes(
+        pool: &sqlx::SqlitePool,
+        statuses: &[ExecutionProcessStatus],
+    ) -> (Uuid, Vec<Uuid>) {
+        let project_id = seed_project(pool).await;
+        let task_id = seed_task(pool, project_id, "Task").await;
+        let attempt_id = seed_task_attempt(pool, task_id, BaseCodingAgent::ClaudeCode).await;
+        let mut process_ids = Vec::new();
+        for status in statuses {
+            process_ids.push(
+                seed_execution_process(
+                    pool,
+                    attempt_id,
+                    ExecutionProcessRunReason::CodingAgent,
+                    status.clone(),
+                )
+                .await,
+            );
+        }
+        (project_id, process_ids)
+    }
+
+    #[tokio::test]
+    async fn stop_project_processes_kills_only_that_projects_running_processes() {
+        let pool = test_pool().await;
+        let (project_id, processes) = seed_project_processes(
+            &pool,
+            &[
+                ExecutionProcessStatus::Running,
+                ExecutionProcessStatus::Completed,
+                ExecutionProcessStatus::Running,
+            ],
+        )
+        .await;
+        seed_project_processes(&pool, &[ExecutionProcessStatus::Running]).await;
+        let container = StubContainer::new(pool, None);
+
+        container.stop_project_processes(project_id).await.unwrap();
+
+        assert_eq!(
+            container.stopped(),
+            vec![
+                (processes[0], ExecutionProcessStatus::Killed),
+                (processes[2], ExecutionProcessStatus::Killed),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stop_project_processes_continues_after_a_failed_stop() {
+        let pool = test_pool().await;
+        let (project_id, processes) = seed_project_processes(
+            &pool,
+            &[
+                ExecutionProcessStatus::Running,
+                ExecutionProcessStatus::Running,
+            ],
+        )
+        .await;
+        let container = StubContainer::new(pool, Some(processes[0]));
+
+        container.stop_project_processes(project_id).await.unwrap();
+
+        assert_eq!(
+            container.stopped(),
+            vec![
+                (processes[0], ExecutionProcessStatus::Killed),
+                (processes[1], ExecutionProcessStatus::Killed),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stop_project_processes_is_a_noop_when_nothing_runs() {
+        let pool = test_pool().await;
+        let (project_id, _) =
+            seed_project_processes(&pool, &[ExecutionProcessStatus::Completed]).await;
+        let container = StubContainer::new(pool, None);
+
+        container.stop_project_processes(project_id).await.unwrap();
+
+        assert!(container.stopped().is_empty());
+    }
+}
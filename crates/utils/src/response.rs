@@ -7,6 +7,14 @@ pub struct ApiResponse<T, E = T> {
     data: Option<T>,
     error_data: Option<E>,
     message: Option<String>,
+    /// Stable, machine-readable error code; omitted when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    code: Option<String>,
+    /// X-Request-Id of the request that produced this error; omitted when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    request_id: Option<String>,
 }
 
 impl<T, E> ApiResponse<T, E> {
@@ -17,6 +25,8 @@ impl<T, E> ApiResponse<T, E> {
             data: Some(data),
             message: None,
             error_data: None,
+            code: None,
+            request_id: None,
         }
     }
 
@@ -27,6 +37,20 @@ impl<T, E> ApiResponse<T, E> {
             data: None,
             message: Some(message.to_string()),
             error_data: None,
+            code: None,
+            request_id: None,
+        }
+    }
+
+    /// Creates an error response, with `message`, a machine-readable `code` and no data.
+    pub fn error_with_code(message: &str, code: &str) -> Self {
+        ApiResponse {
+            success: false,
+            data: None,
+            message: Some(message.to_string()),
+            error_data: None,
+            code: Some(code.to_string()),
+            request_id: None,
         }
     }
     /// Creates an error response, with no `data`, no `message`, but with arbitrary `error_data`.
@@ -36,9 +60,17 @@ impl<T, E> ApiResponse<T, E> {
             data: None,
             error_data: Some(data),
             message: None,
+            code: None,
+            request_id: None,
         }
     }
 
+    /// Attaches the id of the request this response answers, if known.
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
     /// Returns true if the response was successful.
     pub fn is_success(&self) -> bool {
         self.success
@@ -53,4 +85,43 @@ impl<T, E> ApiResponse<T, E> {
     pub fn message(&self) -> Option<&str> {
         self.message.as_deref()
     }
+
+    /// Returns the machine-readable error code if present.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Returns the request id if present.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_is_omitted_unless_set() {
+        let plain = serde_json::to_value(ApiResponse::<()>::error("Not found")).unwrap();
+        assert!(plain.get("code").is_none());
+        assert!(plain.get("request_id").is_none());
+
+        let coded =
+            serde_json::to_value(ApiResponse::<()>::error_with_code("Not found", "NotFound"))
+                .unwrap();
+        assert_eq!(coded["code"], "NotFound");
+        assert_eq!(coded["message"], "Not found");
+    }
+
+    #[test]
+    fn deserializes_responses_without_code() {
+        let response: ApiResponse<()> = serde_json::from_str(
+            r#"{"success":false,"data":null,"error_data":null,"message":"boom"}"#,
+        )
+        .unwrap();
+        assert_eq!(response.code(), None);
+        assert_eq!(response.request_id(), None);
+        assert_eq!(response.message(), Some("boom"));
+    }
 }